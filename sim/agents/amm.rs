@@ -0,0 +1,104 @@
+use arbiter_core::math::{float_to_wad, wad_to_float};
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+
+pub fn to_wad(amount: U256, decimals: u8) -> U256 {
+    amount * U256::exp10(18usize.saturating_sub(decimals as usize))
+}
+
+pub fn from_wad(amount: U256, decimals: u8) -> U256 {
+    amount / U256::exp10(18usize.saturating_sub(decimals as usize))
+}
+
+pub struct AmmPool {
+    pub symbol_x: String,
+    pub symbol_y: String,
+    pub decimals_x: u8,
+    pub decimals_y: u8,
+    pub reserve_x: U256,
+    pub reserve_y: U256,
+    pub fee: f64,
+    pub curve: PricingCurve,
+    pub sold: U256,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum PricingCurve {
+    ConstantProduct,
+    PiecewiseLinear {
+        start_price: f64,
+        end_price: f64,
+        sell_amount: f64,
+    },
+}
+
+impl AmmPool {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        symbol_x: &str,
+        symbol_y: &str,
+        decimals_x: u8,
+        decimals_y: u8,
+        reserve_x: U256,
+        reserve_y: U256,
+        fee: f64,
+        curve: PricingCurve,
+    ) -> Self {
+        Self {
+            symbol_x: symbol_x.to_string(),
+            symbol_y: symbol_y.to_string(),
+            decimals_x,
+            decimals_y,
+            reserve_x: to_wad(reserve_x, decimals_x),
+            reserve_y: to_wad(reserve_y, decimals_y),
+            fee,
+            curve,
+            sold: U256::zero(),
+        }
+    }
+
+    pub fn swap_x_for_y(&mut self, dx: U256) -> U256 {
+        let dx = to_wad(dx, self.decimals_x);
+        let dy = match self.curve {
+            PricingCurve::ConstantProduct => {
+                let dx_after_fee =
+                    dx * (U256::exp10(18) - float_to_wad(self.fee)) / U256::exp10(18);
+                let k = self.reserve_x * self.reserve_y;
+                self.reserve_y - k / (self.reserve_x + dx_after_fee)
+            }
+            PricingCurve::PiecewiseLinear {
+                start_price,
+                end_price,
+                sell_amount,
+            } => {
+                let price_at = |sold: f64| {
+                    start_price + (end_price - start_price) * sold.min(sell_amount) / sell_amount
+                };
+                let sold_before = wad_to_float(self.sold);
+                let sold_after = sold_before + wad_to_float(dx);
+                let proceeds = 0.5 * (price_at(sold_before) + price_at(sold_after)) * wad_to_float(dx);
+                self.sold += dx;
+                float_to_wad(proceeds)
+            }
+        };
+        self.reserve_x += dx;
+        self.reserve_y = self.reserve_y.saturating_sub(dy);
+        from_wad(dy, self.decimals_y)
+    }
+
+    pub fn swap_y_for_x(&mut self, dx: U256) -> U256 {
+        let dx = to_wad(dx, self.decimals_x).min(self.reserve_x.saturating_sub(1));
+        let k = self.reserve_x * self.reserve_y;
+        let new_reserve_x = self.reserve_x - dx;
+        let dy_needed = k / new_reserve_x - self.reserve_y;
+        let dy = dy_needed * U256::exp10(18) / (U256::exp10(18) - float_to_wad(self.fee));
+        self.reserve_x = new_reserve_x;
+        self.reserve_y += dy;
+        from_wad(dy, self.decimals_y)
+    }
+
+    pub fn spot_price(&self) -> f64 {
+        wad_to_float(self.reserve_y) / wad_to_float(self.reserve_x)
+    }
+}
@@ -0,0 +1,42 @@
+use ethers::types::U256;
+
+use crate::agents::amm::AmmPool;
+
+pub struct Arbitrageur {
+    pub threshold: f64,
+    pub max_trade: U256,
+}
+
+impl Arbitrageur {
+    pub fn new(threshold: f64, max_trade: U256) -> Self {
+        Self {
+            threshold,
+            max_trade,
+        }
+    }
+
+    pub fn arbitrage(&self, pool: &mut AmmPool, oracle_price: f64) -> U256 {
+        let spot = pool.spot_price();
+        if (spot - oracle_price).abs() / oracle_price < self.threshold {
+            return U256::zero();
+        }
+
+        let reserve_x = arbiter_core::math::wad_to_float(pool.reserve_x);
+        let k = reserve_x * arbiter_core::math::wad_to_float(pool.reserve_y);
+        let target_x = (k / oracle_price).sqrt();
+        let dx = U256::from(
+            ((target_x - reserve_x).abs() * 10f64.powi(pool.decimals_x as i32)) as u128,
+        )
+        .min(self.max_trade);
+        if dx.is_zero() {
+            return U256::zero();
+        }
+
+        if target_x < reserve_x {
+            pool.swap_y_for_x(dx);
+        } else {
+            pool.swap_x_for_y(dx);
+        }
+        dx
+    }
+}
@@ -0,0 +1,53 @@
+use ethers::types::{Address, U256};
+
+pub struct DutchAuction {
+    pub account: Address,
+    pub repay_market: Address,
+    pub seize_market: Address,
+    pub start_step: u64,
+    pub start_price: f64,
+    pub decay_rate: f64,
+    pub duration: f64,
+    pub min_price: f64,
+    pub geometric: bool,
+    pub remaining_collateral: U256,
+}
+
+impl DutchAuction {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        account: Address,
+        repay_market: Address,
+        seize_market: Address,
+        start_step: u64,
+        start_price: f64,
+        decay_rate: f64,
+        duration: f64,
+        min_price: f64,
+        geometric: bool,
+        remaining_collateral: U256,
+    ) -> Self {
+        Self {
+            account,
+            repay_market,
+            seize_market,
+            start_step,
+            start_price,
+            decay_rate,
+            duration,
+            min_price,
+            geometric,
+            remaining_collateral,
+        }
+    }
+
+    pub fn offered_price(&self, current_step: u64) -> f64 {
+        let elapsed = (current_step - self.start_step) as f64;
+        let price = if self.geometric {
+            self.start_price * (1.0 - self.decay_rate).powf(elapsed / self.duration)
+        } else {
+            self.start_price * (1.0 - self.decay_rate * elapsed / self.duration)
+        };
+        price.max(self.min_price)
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use arbiter_core::{environment::Environment, math::wad_to_float, middleware::RevmMiddleware};
+use ethers::types::{Address, U256};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::bindings::{auditor::Auditor, market::Market, mock_erc20::MockERC20};
+
+pub struct Borrower {
+    pub address: Address,
+    pub collateral_market: Address,
+    pub borrow_market: Address,
+    pub target_ltv: f64,
+}
+
+pub struct BorrowerPopulation {
+    pub count: usize,
+    pub min_collateral: U256,
+    pub max_collateral: U256,
+    pub min_target_ltv: f64,
+    pub max_target_ltv: f64,
+    pub seed: u64,
+}
+
+impl BorrowerPopulation {
+    pub async fn spawn(
+        &self,
+        environment: &Environment,
+        auditor: Auditor<RevmMiddleware>,
+        markets: &[(MockERC20<RevmMiddleware>, Market<RevmMiddleware>)],
+        adjust_factors: &[f64],
+        oracle_prices: &[f64],
+    ) -> Result<Vec<Borrower>> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut borrowers = Vec::with_capacity(self.count);
+        for i in 0..self.count {
+            let collateral_idx = rng.gen_range(0..markets.len());
+            let borrow_idx = (0..markets.len())
+                .filter(|idx| *idx != collateral_idx)
+                .nth(rng.gen_range(0..markets.len() - 1))
+                .unwrap();
+            let target_ltv = rng.gen_range(self.min_target_ltv..self.max_target_ltv);
+
+            let client = RevmMiddleware::new(environment, Some(&format!("borrower_{i}")))?;
+            let (collateral_asset, collateral_market) = &markets[collateral_idx];
+            let collateral_decimals = collateral_asset.decimals().call().await?;
+            let collateral_amount = U256::from(
+                rng.gen_range(
+                    wad_to_float(self.min_collateral)..wad_to_float(self.max_collateral),
+                ) * 10f64.powi(collateral_decimals.as_u32() as i32),
+            );
+            collateral_asset
+                .mint(client.address(), collateral_amount)
+                .send()
+                .await?
+                .await?;
+            MockERC20::new(collateral_asset.address(), client.clone())
+                .approve(collateral_market.address(), collateral_amount)
+                .send()
+                .await?
+                .await?;
+            Market::new(collateral_market.address(), client.clone())
+                .deposit(collateral_amount, client.address())
+                .send()
+                .await?
+                .await?;
+            Auditor::new(auditor.address(), client.clone())
+                .enter_market(collateral_market.address())
+                .send()
+                .await?
+                .await?;
+
+            let (collateral_usd, _) = auditor
+                .account_liquidity(client.address(), Address::zero(), U256::zero())
+                .call()
+                .await?;
+            let (borrow_asset, borrow_market) = &markets[borrow_idx];
+            let target_debt_usd =
+                wad_to_float(collateral_usd) * target_ltv * adjust_factors[borrow_idx];
+            let borrow_decimals = borrow_asset.decimals().call().await?;
+            let borrow_amount = U256::from(
+                (target_debt_usd / oracle_prices[borrow_idx] * 10f64.powi(borrow_decimals.as_u32() as i32))
+                    as u128,
+            );
+            if !borrow_amount.is_zero() {
+                Market::new(borrow_market.address(), client.clone())
+                    .borrow(borrow_amount, client.address(), client.address())
+                    .send()
+                    .await?
+                    .await?;
+            }
+
+            borrowers.push(Borrower {
+                address: client.address(),
+                collateral_market: collateral_market.address(),
+                borrow_market: borrow_market.address(),
+                target_ltv,
+            });
+        }
+        Ok(borrowers)
+    }
+}
@@ -1,26 +1,51 @@
+use std::collections::HashMap;
+
 use anyhow::{Ok, Result};
-use arbiter_core::{math::wad_to_float, middleware::RevmMiddleware};
-use ethers::types::{Address, U256};
+use arbiter_core::{
+    math::{float_to_wad, wad_to_float},
+    middleware::RevmMiddleware,
+};
+use ethers::{
+    contract::EthLogDecode,
+    types::{Address, U256},
+};
 use log::info;
 
-use crate::bindings::{
-    auditor::Auditor,
-    market::Market,
-    mock_erc20::MockERC20,
-    previewer::{MarketAccount, Previewer},
+use crate::{
+    agents::{amm::AmmPool, auction::DutchAuction},
+    bindings::{
+        auditor::Auditor,
+        market::{LiquidateFilter, Market},
+        mock_erc20::MockERC20,
+        previewer::{MarketAccount, Previewer},
+    },
+    startup::LiquidationMode,
 };
 
-pub struct Liquidator<const L: usize> {
+pub struct LiquidatorConfig {
+    pub close_factor: f64,
+    pub close_amount: f64,
+    pub auction_profit_margin: f64,
+    pub liquidation_modes: HashMap<Address, LiquidationMode>,
+}
+
+pub struct Liquidator {
     pub auditor: Auditor<RevmMiddleware>,
     pub previewer: Previewer<RevmMiddleware>,
-    pub accounts: [Address; L],
+    pub accounts: Vec<Address>,
+    pub config: LiquidatorConfig,
+    pub pools: HashMap<(Address, Address), AmmPool>,
+    pub auctions: HashMap<Address, DutchAuction>,
+    pub step: u64,
 }
 
-impl<const L: usize> Liquidator<L> {
+impl Liquidator {
     pub async fn new(
         auditor: Auditor<RevmMiddleware>,
         previewer: Previewer<RevmMiddleware>,
-        accounts: [Address; L],
+        accounts: Vec<Address>,
+        pools: HashMap<(Address, Address), AmmPool>,
+        config: LiquidatorConfig,
     ) -> Result<Self> {
         let client = auditor.client();
         for market in auditor.all_markets().call().await? {
@@ -36,10 +61,14 @@ impl<const L: usize> Liquidator<L> {
             auditor,
             previewer,
             accounts,
+            config,
+            pools,
+            auctions: HashMap::new(),
+            step: 0,
         })
     }
 
-    pub async fn check_liquidations(&self) -> Result<()> {
+    pub async fn check_liquidations(&mut self) -> Result<()> {
         for account in &self.accounts {
             let (collateral, debt) = self
                 .auditor
@@ -47,6 +76,7 @@ impl<const L: usize> Liquidator<L> {
                 .call()
                 .await?;
             if collateral >= debt {
+                self.auctions.remove(account);
                 continue;
             }
             info!(
@@ -59,7 +89,7 @@ impl<const L: usize> Liquidator<L> {
                 }
             );
             let exactly: Vec<MarketAccount> = self.previewer.exactly(*account).call().await?;
-            let repay_market = exactly
+            let repay_account = exactly
                 .iter()
                 .reduce(|a, b| {
                     if (b.floating_borrow_assets
@@ -85,9 +115,9 @@ impl<const L: usize> Liquidator<L> {
                         a
                     }
                 })
-                .unwrap()
-                .market;
-            let seize_market = exactly
+                .unwrap();
+            let repay_market = repay_account.market;
+            let seize_account = exactly
                 .iter()
                 .reduce(|a, b| {
                     if b.is_collateral
@@ -100,14 +130,132 @@ impl<const L: usize> Liquidator<L> {
                         a
                     }
                 })
-                .unwrap()
-                .market;
-            Market::new(repay_market, self.auditor.client().clone())
-                .liquidate(*account, U256::MAX, seize_market)
-                .send()
-                .await?
-                .await?;
+                .unwrap();
+            let seize_market = seize_account.market;
+
+            let total_debt = repay_account.floating_borrow_assets
+                + repay_account
+                    .fixed_borrow_positions
+                    .iter()
+                    .fold(U256::zero(), |debt, position| {
+                        debt + position.position.principal + position.position.fee
+                    });
+            let max_repay =
+                total_debt * float_to_wad(self.config.close_factor) / U256::exp10(18);
+            let remaining_debt_usd = (total_debt - max_repay) * repay_account.usd_price
+                / U256::exp10(repay_account.decimals.into());
+            let repay_amount = if remaining_debt_usd < float_to_wad(self.config.close_amount) {
+                U256::MAX
+            } else {
+                max_repay
+            };
+
+            match self.config.liquidation_modes.get(&seize_market).copied() {
+                Some(LiquidationMode::Auction {
+                    start_discount,
+                    decay_rate,
+                    duration,
+                    min_price,
+                    geometric,
+                }) => {
+                    let oracle_price = wad_to_float(seize_account.usd_price);
+                    let step = self.step;
+                    let auction = self.auctions.entry(*account).or_insert_with(|| {
+                        DutchAuction::new(
+                            *account,
+                            repay_market,
+                            seize_market,
+                            step,
+                            oracle_price * (1.0 - start_discount),
+                            decay_rate,
+                            duration,
+                            oracle_price * min_price,
+                            geometric,
+                            seize_account.floating_deposit_assets,
+                        )
+                    });
+                    let offered_price = auction.offered_price(step);
+                    info!(
+                        "account: {}, auction offered price: {}, oracle price: {}",
+                        account, offered_price, oracle_price
+                    );
+                    if offered_price <= oracle_price * (1.0 - self.config.auction_profit_margin) {
+                        let seized = self
+                            .execute_liquidation(
+                                *account,
+                                repay_market,
+                                seize_market,
+                                repay_amount,
+                                total_debt,
+                            )
+                            .await?;
+                        if let Some(auction) = self.auctions.get_mut(account) {
+                            auction.remaining_collateral =
+                                auction.remaining_collateral.saturating_sub(seized);
+                            if auction.remaining_collateral.is_zero() {
+                                self.auctions.remove(account);
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    self.execute_liquidation(
+                        *account,
+                        repay_market,
+                        seize_market,
+                        repay_amount,
+                        total_debt,
+                    )
+                    .await?;
+                }
+            }
         }
+        self.step += 1;
         Ok(())
     }
+
+    async fn execute_liquidation(
+        &mut self,
+        account: Address,
+        repay_market: Address,
+        seize_market: Address,
+        repay_amount: U256,
+        total_debt: U256,
+    ) -> Result<U256> {
+        let market = Market::new(repay_market, self.auditor.client().clone());
+        let receipt = market
+            .liquidate(account, repay_amount, seize_market)
+            .send()
+            .await?
+            .await?
+            .expect("liquidate transaction dropped from the mempool");
+        let LiquidateFilter {
+            assets: repaid,
+            seized_assets: seized,
+            ..
+        } = receipt
+            .logs
+            .iter()
+            .find_map(|log| LiquidateFilter::decode_log(&log.clone().into()).ok())
+            .expect("liquidate did not emit a Liquidate event");
+        info!(
+            "account: {}, repay_market: {}, seize_market: {}, repaid: {}, seized: {}, full_close: {}",
+            account,
+            repay_market,
+            seize_market,
+            repaid,
+            seized,
+            repay_amount == U256::MAX || repaid >= total_debt,
+        );
+
+        if let Some(pool) = self.pools.get_mut(&(seize_market, repay_market)) {
+            let proceeds = pool.swap_x_for_y(seized);
+            let net = proceeds.as_u128() as i128 - repaid.as_u128() as i128;
+            info!(
+                "account: {}, seized collateral sold for {}, repaid {}, net profit: {}",
+                account, proceeds, repaid, net
+            );
+        }
+        Ok(seized)
+    }
 }
@@ -0,0 +1,6 @@
+pub mod amm;
+pub mod arbitrageur;
+pub mod auction;
+pub mod borrower;
+pub mod liquidator;
+pub mod price_changer;
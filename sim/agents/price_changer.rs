@@ -5,6 +5,8 @@ use arbiter_core::{
 };
 use ethers::types::I256;
 use log::info;
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::{Distribution, LogNormal, Poisson};
 use serde::{Deserialize, Serialize};
 
 use crate::bindings::mock_price_feed::MockPriceFeed;
@@ -27,16 +29,38 @@ impl PriceChanger {
             t_n,
             n_steps,
             seed,
+            process,
         } = params;
-        let process = OrnsteinUhlenbeck::new(mean, std_dev, theta);
+        let ou = OrnsteinUhlenbeck::new(mean, std_dev, theta);
 
-        let trajectory = match seed {
-            Some(seed) => {
-                process.seedable_euler_maruyama(initial_price, t_0, t_n, n_steps, 1, false, seed)
-            }
-            None => process.euler_maruyama(initial_price, t_0, t_n, n_steps, 1, false),
+        let mut trajectory = match seed {
+            Some(seed) => ou.seedable_euler_maruyama(initial_price, t_0, t_n, n_steps, 1, false, seed),
+            None => ou.euler_maruyama(initial_price, t_0, t_n, n_steps, 1, false),
         };
 
+        if let PriceProcessKind::JumpDiffusion {
+            lambda,
+            mu_j,
+            sigma_j,
+        } = process
+        {
+            let dt = (t_n - t_0) / n_steps as f64;
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            };
+            let jump_count = Poisson::new(lambda * dt).unwrap();
+            let jump_size = LogNormal::new(mu_j, sigma_j).unwrap();
+            let mut level_shift = 1.0;
+            for price in trajectory.paths[0].iter_mut().skip(1) {
+                let jumps = jump_count.sample(&mut rng) as u32;
+                for _ in 0..jumps {
+                    level_shift *= jump_size.sample(&mut rng);
+                }
+                *price *= level_shift;
+            }
+        }
+
         Self {
             symbol: symbol.to_string(),
             trajectory,
@@ -56,6 +80,10 @@ impl PriceChanger {
         self.index += 1;
         Ok(())
     }
+
+    pub fn current_price(&self) -> f64 {
+        self.trajectory.paths[0][self.index - 1]
+    }
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
@@ -68,4 +96,16 @@ pub struct PriceProcessParameters {
     pub t_n: f64,
     pub n_steps: usize,
     pub seed: Option<u64>,
+    pub process: PriceProcessKind,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum PriceProcessKind {
+    OrnsteinUhlenbeck,
+    JumpDiffusion {
+        lambda: f64,
+        mu_j: f64,
+        sigma_j: f64,
+    },
 }
@@ -1,4 +1,4 @@
-use std::process::Command;
+use std::{collections::HashMap, process::Command};
 
 use anyhow::{Ok, Result};
 use arbiter_core::{
@@ -12,12 +12,16 @@ use log::info;
 use serde_json::from_slice;
 
 use crate::{
-    agents::{liquidator::Liquidator, price_changer::PriceProcessParameters},
+    agents::{
+        amm::{AmmPool, PricingCurve},
+        arbitrageur::Arbitrageur,
+        borrower::BorrowerPopulation,
+        liquidator::{Liquidator, LiquidatorConfig},
+        price_changer::{PriceProcessKind, PriceProcessParameters},
+    },
     bindings::{
         auditor::{Auditor, LiquidationIncentive},
         erc1967_proxy::ERC1967Proxy,
-        market::Market,
-        mock_erc20::MockERC20,
         previewer::Previewer,
     },
     startup::{deploy_market, Finance},
@@ -104,6 +108,14 @@ pub async fn main() -> Result<()> {
                 t_n: 100.0,
                 n_steps: 2_500,
                 seed: None,
+                process: match symbol.as_str() {
+                    "WETH" => PriceProcessKind::JumpDiffusion {
+                        lambda: 0.1,
+                        mu_j: 0.0,
+                        sigma_j: 0.05,
+                    },
+                    _ => PriceProcessKind::OrnsteinUhlenbeck,
+                },
             },
         )
     }))
@@ -116,64 +128,114 @@ pub async fn main() -> Result<()> {
     }
     listener.run()?;
 
-    let alice = RevmMiddleware::new(&environment, Some("alice"))?;
-    markets[0]
-        .0
-        .mint(alice.address(), U256::exp10(18) * 1_000_000)
-        .send()
-        .await?
-        .await?;
-    MockERC20::new(markets[0].0.address(), alice.clone())
-        .approve(markets[0].1.address(), U256::MAX)
-        .send()
-        .await?
-        .await?;
-    Market::new(markets[0].1.address(), alice.clone())
-        .deposit(U256::exp10(18) * 1_000_000, alice.address())
-        .send()
-        .await?
-        .await?;
-    Auditor::new(auditor.address(), alice.clone())
-        .enter_market(markets[0].1.address())
-        .send()
-        .await?
-        .await?;
-    markets[1]
-        .0
-        .mint(deployer.address(), U256::exp10(6) * 1_000_000)
-        .send()
-        .await?
-        .await?;
-    markets[1]
-        .0
-        .approve(markets[1].1.address(), U256::MAX)
-        .send()
-        .await?
-        .await?;
-    markets[1]
-        .1
-        .deposit(U256::exp10(6) * 1_000_000, deployer.address())
-        .send()
-        .await?
-        .await?;
-    Market::new(markets[1].1.address(), alice.clone())
-        .borrow(U256::exp10(6) * 810_000, alice.address(), alice.address())
-        .send()
-        .await?
-        .await?;
+    for (asset, market, _) in &markets {
+        let decimals = asset.decimals().call().await?;
+        let liquidity = U256::exp10(decimals.into()) * 1_000_000;
+        asset.mint(deployer.address(), liquidity).send().await?.await?;
+        asset
+            .approve(market.address(), liquidity)
+            .send()
+            .await?
+            .await?;
+        market
+            .deposit(liquidity, deployer.address())
+            .send()
+            .await?
+            .await?;
+    }
+
+    let adjust_factors = markets
+        .iter()
+        .map(|(_, _, price_changer)| finance.markets[&price_changer.symbol].adjust_factor)
+        .collect::<Vec<_>>();
+    let oracle_prices = markets
+        .iter()
+        .map(|(_, _, price_changer)| price_changer.current_price())
+        .collect::<Vec<_>>();
+    let market_pairs = markets
+        .iter()
+        .map(|(asset, market, _)| (asset.clone(), market.clone()))
+        .collect::<Vec<_>>();
+    let borrowers = BorrowerPopulation {
+        count: 10,
+        min_collateral: U256::exp10(18) / 10,
+        max_collateral: U256::exp10(18) * 10,
+        min_target_ltv: 0.3,
+        max_target_ltv: 0.8,
+        seed: 1,
+    }
+    .spawn(
+        &environment,
+        auditor.clone(),
+        &market_pairs,
+        &adjust_factors,
+        &oracle_prices,
+    )
+    .await?;
+
+    let liquidation_modes = markets
+        .iter()
+        .map(|(_, market, price_changer)| {
+            (
+                market.address(),
+                finance.markets[&price_changer.symbol].liquidation_mode,
+            )
+        })
+        .collect();
+
+    let weth_idx = markets
+        .iter()
+        .position(|(_, _, price_changer)| price_changer.symbol == "WETH")
+        .expect("WETH market not deployed");
+    let usdc_idx = markets
+        .iter()
+        .position(|(_, _, price_changer)| price_changer.symbol == "USDC")
+        .expect("USDC market not deployed");
 
-    let liquidator = Liquidator::new(
+    let mut pools = HashMap::new();
+    pools.insert(
+        (markets[weth_idx].1.address(), markets[usdc_idx].1.address()),
+        AmmPool::new(
+            "WETH",
+            "USDC",
+            18,
+            6,
+            U256::exp10(18) * 5_000,
+            U256::exp10(6) * 9_250_000,
+            0.003,
+            PricingCurve::ConstantProduct,
+        ),
+    );
+
+    let mut liquidator = Liquidator::new(
         auditor.clone(),
         Previewer::deploy(deployer.clone(), (auditor.address(), Address::zero()))?
             .send()
             .await?,
-        [alice.address()],
+        borrowers.iter().map(|borrower| borrower.address).collect(),
+        pools,
+        LiquidatorConfig {
+            close_factor: finance.close_factor,
+            close_amount: finance.close_amount,
+            auction_profit_margin: finance.auction_profit_margin,
+            liquidation_modes,
+        },
     )
     .await?;
+    let arbitrageur = Arbitrageur::new(0.001, U256::exp10(18) * 50);
     for _ in 1..markets[0].2.trajectory.paths[0].len() {
         for (_, _, price_changer) in &mut markets {
             price_changer.update_price().await?;
         }
+        if let Some(pool) = liquidator
+            .pools
+            .get_mut(&(markets[weth_idx].1.address(), markets[usdc_idx].1.address()))
+        {
+            let volume = arbitrageur.arbitrage(pool, markets[weth_idx].2.current_price());
+            if !volume.is_zero() {
+                info!("arbitrage volume: {volume}");
+            }
+        }
         liquidator.check_liquidations().await?;
     }
     environment.stop()?;
@@ -110,6 +110,9 @@ pub async fn deploy_market(
 pub struct Finance {
     pub treasury_fee_rate: f64,
     pub liquidation_incentive: LiquidationIncentive,
+    pub close_factor: f64,
+    pub close_amount: f64,
+    pub auction_profit_margin: f64,
     pub penalty_rate_per_day: f64,
     pub backup_fee_rate: f64,
     pub reserve_factor: f64,
@@ -161,6 +164,28 @@ pub struct MarketParameters {
     pub floating_curve: InterestRateModelParameters,
     pub fixed_curve: InterestRateModelParameters,
     pub rewards: HashMap<String, Rewards>,
+    pub liquidation_mode: LiquidationMode,
+}
+
+/// How an unhealthy position in a market is liquidated.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum LiquidationMode {
+    /// Seize and repay immediately at the protocol's fixed `liquidation_incentive`.
+    Instant,
+    /// Open a descending-price Dutch auction instead of liquidating immediately.
+    Auction {
+        /// Discount off the oracle price the auction opens at.
+        start_discount: f64,
+        /// Fraction of the starting price shed per `duration` elapsed.
+        decay_rate: f64,
+        /// Number of price steps over which `decay_rate` of the starting price is shed.
+        duration: f64,
+        /// Floor price, as a fraction of the oracle price, the auction never decays past.
+        min_price: f64,
+        /// Use geometric instead of linear decay.
+        geometric: bool,
+    },
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]